@@ -53,7 +53,7 @@ impl GameEvent {
 }
 
 /// Response from the `init` command.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InitResponse {
     /// Unique identifier for this game
     pub game_id: i32,
@@ -64,7 +64,7 @@ pub struct InitResponse {
 }
 
 /// Current game status returned by `get_status`.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GameStatus {
     /// Whether connected to the game's API/client
     pub connected: bool,
@@ -155,7 +155,9 @@ pub enum MatchDataMessage {
     ///
     /// The daemon will:
     /// 1. Create match row if it doesn't exist (lazy creation)
-    /// 2. UPSERT stats to the summary table (`p{guid}_{subpack}_match_details`)
+    /// 2. UPSERT stats to the summary table (`p{guid}_{subpack}_match_details`),
+    ///    applying `merge` (defaulting missing keys to `MergeOp::Replace`) so a
+    ///    gamepack can send incremental deltas instead of full running totals
     WriteStats {
         /// Subpack index (0 = default, 1+ = additional subpacks)
         subpack: u8,
@@ -172,6 +174,9 @@ pub enum MatchDataMessage {
         result: Option<String>,
         /// Stats to write (keys must match columns declared in subpack's schema)
         stats: HashMap<String, serde_json::Value>,
+        /// Per-field merge semantics for `stats` (keys not listed here default to `Replace`)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        merge: Option<HashMap<String, MergeOp>>,
     },
 
     /// Append events to match timeline.
@@ -201,9 +206,98 @@ pub enum MatchDataMessage {
         /// Optional final stats to overwrite summary table
         #[serde(skip_serializing_if = "Option::is_none")]
         final_stats: Option<HashMap<String, serde_json::Value>>,
+        /// How the match actually ended, so the daemon can tell a clean
+        /// finish apart from a crash, disconnect, or stale recovery.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        termination: Option<MatchTermination>,
     },
 }
 
+/// How to combine an incoming `WriteStats` field with the value already
+/// stored in the summary table, instead of always overwriting it.
+///
+/// Lets a gamepack stream "+1 kill" on every event rather than tracking and
+/// resending a running total itself, which also avoids races across bursts
+/// of concurrent `WriteStats` calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeOp {
+    /// Overwrite the existing value (default when a field has no entry in `merge`)
+    Replace,
+    /// Add the incoming numeric value to the existing one (missing existing value treated as 0)
+    Sum,
+    /// Keep whichever of the incoming/existing numeric value is larger
+    Max,
+    /// Keep whichever of the incoming/existing numeric value is smaller
+    Min,
+    /// Overwrite the existing value (alias of `Replace`, for gamepacks that track "most recent wins" explicitly)
+    Last,
+    /// Push the incoming value into the existing JSON array (missing existing value treated as an empty array)
+    Append,
+}
+
+/// Describes how a match ended, separate from `summary_source` (which only
+/// says where the final stats came from).
+///
+/// Lets the daemon mark matches differently in its summary table and decide
+/// whether to keep partial clips, e.g. a `crashed` match with
+/// `had_capture_errors` may still be worth keeping a partial clip for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchTermination {
+    /// Why the match ended: "completed" | "crashed" | "abandoned" | "stale_recovery"
+    pub reason: String,
+    /// Whether the gamepack hit errors while capturing, regardless of how the match ended
+    pub had_capture_errors: bool,
+    /// Optional human-readable detail (e.g. panic message, disconnect reason)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_detail: Option<String>,
+}
+
+impl MatchTermination {
+    /// Create a termination for a clean, natural game end.
+    pub fn completed() -> Self {
+        Self {
+            reason: "completed".to_string(),
+            had_capture_errors: false,
+            error_detail: None,
+        }
+    }
+
+    /// Create a termination for a gamepack or game client crash.
+    pub fn crashed(error_detail: impl Into<String>) -> Self {
+        Self {
+            reason: "crashed".to_string(),
+            had_capture_errors: true,
+            error_detail: Some(error_detail.into()),
+        }
+    }
+
+    /// Create a termination for a player-abandoned match (e.g. remake, leave).
+    pub fn abandoned() -> Self {
+        Self {
+            reason: "abandoned".to_string(),
+            had_capture_errors: false,
+            error_detail: None,
+        }
+    }
+
+    /// Create a termination for a match recovered as stale (daemon gave up waiting).
+    pub fn stale_recovery() -> Self {
+        Self {
+            reason: "stale_recovery".to_string(),
+            had_capture_errors: false,
+            error_detail: None,
+        }
+    }
+
+    /// Mark that capture errors occurred, regardless of `reason`.
+    pub fn with_capture_errors(mut self, error_detail: impl Into<String>) -> Self {
+        self.had_capture_errors = true;
+        self.error_detail = Some(error_detail.into());
+        self
+    }
+}
+
 impl MatchDataMessage {
     /// Create a WriteStats message.
     pub fn write_stats(
@@ -218,6 +312,27 @@ impl MatchDataMessage {
             duration_secs: None,
             result: None,
             stats,
+            merge: None,
+        }
+    }
+
+    /// Create a WriteStats message with per-field merge semantics, so a
+    /// gamepack can stream incremental deltas (e.g. "+1 kill") instead of
+    /// recomputing and resending a running total every event.
+    pub fn write_stats_with_merge(
+        subpack: u8,
+        external_match_id: impl Into<String>,
+        stats: HashMap<String, serde_json::Value>,
+        merge: HashMap<String, MergeOp>,
+    ) -> Self {
+        Self::WriteStats {
+            subpack,
+            external_match_id: external_match_id.into(),
+            played_at: None,
+            duration_secs: None,
+            result: None,
+            stats,
+            merge: Some(merge),
         }
     }
 
@@ -245,6 +360,7 @@ impl MatchDataMessage {
             external_match_id: external_match_id.into(),
             summary_source: summary_source.into(),
             final_stats: None,
+            termination: None,
         }
     }
 
@@ -260,6 +376,24 @@ impl MatchDataMessage {
             external_match_id: external_match_id.into(),
             summary_source: summary_source.into(),
             final_stats: Some(final_stats),
+            termination: None,
+        }
+    }
+
+    /// Create a SetComplete message with an explicit termination reason.
+    pub fn set_complete_with_termination(
+        subpack: u8,
+        external_match_id: impl Into<String>,
+        summary_source: impl Into<String>,
+        final_stats: Option<HashMap<String, serde_json::Value>>,
+        termination: MatchTermination,
+    ) -> Self {
+        Self::SetComplete {
+            subpack,
+            external_match_id: external_match_id.into(),
+            summary_source: summary_source.into(),
+            final_stats,
+            termination: Some(termination),
         }
     }
 }
@@ -288,6 +422,11 @@ pub struct IsMatchInProgressResponse {
     /// If !still_playing, optionally provide SetComplete message with final stats
     #[serde(skip_serializing_if = "Option::is_none")]
     pub set_complete: Option<MatchDataMessage>,
+    /// If !still_playing, how the daemon should classify the end of this
+    /// recovery (e.g. "stale_recovery" when the gamepack itself can't say
+    /// what happened to it).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub termination: Option<MatchTermination>,
 }
 
 impl IsMatchInProgressResponse {
@@ -296,6 +435,7 @@ impl IsMatchInProgressResponse {
         Self {
             still_playing: true,
             set_complete: None,
+            termination: None,
         }
     }
 
@@ -304,6 +444,7 @@ impl IsMatchInProgressResponse {
         Self {
             still_playing: false,
             set_complete: None,
+            termination: None,
         }
     }
 
@@ -312,6 +453,72 @@ impl IsMatchInProgressResponse {
         Self {
             still_playing: false,
             set_complete: Some(set_complete),
+            termination: None,
+        }
+    }
+
+    /// Create a response for a match the daemon gave up recovering.
+    pub fn ended_stale(termination: MatchTermination) -> Self {
+        Self {
+            still_playing: false,
+            set_complete: None,
+            termination: Some(termination),
+        }
+    }
+}
+
+// ============================================================================
+// CANONICAL MATCH FETCH
+// ============================================================================
+
+/// Daemon → Gamepack: Ask the gamepack to fetch canonical stats for a match
+/// from the game's official API (e.g. a match-v5-style endpoint), rather
+/// than relying on the daemon's own live-captured stats.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FetchCanonicalMatch {
+    /// Subpack index
+    pub subpack: u8,
+    /// Game's native match ID
+    pub external_match_id: String,
+    /// Hint for which region/shard to query the official API against
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region_hint: Option<String>,
+}
+
+/// Gamepack → Daemon: Response to `FetchCanonicalMatch`.
+///
+/// Official APIs commonly publish match records with a delay after the game
+/// ends, so `available: false` comes with `retry_after_secs` instead of the
+/// daemon immediately falling back to live-captured stats.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FetchCanonicalMatchResponse {
+    /// Whether canonical stats were available
+    pub available: bool,
+    /// If !available, how long the daemon should wait before retrying
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after_secs: Option<f64>,
+    /// If available, a fully-populated `SetComplete` with `summary_source: "api"`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub set_complete: Option<MatchDataMessage>,
+}
+
+impl FetchCanonicalMatchResponse {
+    /// Create a response with the fetched canonical match data.
+    pub fn available(set_complete: MatchDataMessage) -> Self {
+        Self {
+            available: true,
+            retry_after_secs: None,
+            set_complete: Some(set_complete),
+        }
+    }
+
+    /// Create a response indicating the official API hasn't published the
+    /// match yet, to be retried after `retry_after_secs`.
+    pub fn not_yet_available(retry_after_secs: f64) -> Self {
+        Self {
+            available: false,
+            retry_after_secs: Some(retry_after_secs),
+            set_complete: None,
         }
     }
 }
@@ -339,6 +546,10 @@ pub struct TimelineEntry {
     /// Only for moments: whether recording was triggered
     #[serde(skip_serializing_if = "Option::is_none")]
     pub trigger_fired: Option<bool>,
+    /// Only for moments: why `trigger_fired` is false, if it failed rather
+    /// than simply not applying (e.g. a capture error, not a missed trigger)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_error: Option<String>,
 }
 
 impl TimelineEntry {
@@ -356,6 +567,7 @@ impl TimelineEntry {
             captured_at: captured_at.into(),
             data,
             trigger_fired: None,
+            trigger_error: None,
         }
     }
 
@@ -372,6 +584,7 @@ impl TimelineEntry {
             captured_at: captured_at.into(),
             data: changed_fields,
             trigger_fired: None,
+            trigger_error: None,
         }
     }
 
@@ -390,13 +603,35 @@ impl TimelineEntry {
             captured_at: captured_at.into(),
             data,
             trigger_fired: Some(trigger_fired),
+            trigger_error: None,
+        }
+    }
+
+    /// Create a moment entry whose trigger failed to fire due to a capture error.
+    pub fn moment_with_error(
+        moment_id: impl Into<String>,
+        game_time_secs: f64,
+        captured_at: impl Into<String>,
+        data: serde_json::Value,
+        trigger_error: impl Into<String>,
+    ) -> Self {
+        Self {
+            entry_type: "moment".to_string(),
+            entry_key: moment_id.into(),
+            game_time_secs,
+            captured_at: captured_at.into(),
+            data,
+            trigger_fired: Some(false),
+            trigger_error: Some(trigger_error.into()),
         }
     }
 }
 
 /// Daemon → Gamepack: Request match timeline data.
 ///
-/// Used for recovery when a gamepack needs to reconstruct match state.
+/// Used for recovery when a gamepack needs to reconstruct match state, either
+/// all at once or paged/windowed via `before`/`after`/`since_secs`/`until_secs`.
+/// Entries are returned sorted by `game_time_secs` then insertion order.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetMatchTimelineRequest {
     /// Subpack index
@@ -406,9 +641,57 @@ pub struct GetMatchTimelineRequest {
     /// Filter by entry types (None = all types)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub entry_types: Option<Vec<String>>,
-    /// Max entries to return (latest N)
+    /// Max entries to return (latest N if no cursor/window is set)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<u32>,
+    /// Only return entries at or after this in-game time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since_secs: Option<f64>,
+    /// Only return entries at or before this in-game time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until_secs: Option<f64>,
+    /// Opaque cursor (from a prior response's `next_cursor`): only return
+    /// entries before this point in the `(captured_at, sequence)` ordering
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// Opaque cursor: only return entries after this point
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+}
+
+impl GetMatchTimelineRequest {
+    /// Request the full (unpaged, unwindowed) timeline for a match.
+    pub fn new(subpack: u8, external_match_id: impl Into<String>) -> Self {
+        Self {
+            subpack,
+            external_match_id: external_match_id.into(),
+            entry_types: None,
+            limit: None,
+            since_secs: None,
+            until_secs: None,
+            before: None,
+            after: None,
+        }
+    }
+
+    /// Page to the entries after a previous response's `next_cursor`.
+    pub fn after_cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.after = Some(cursor.into());
+        self
+    }
+
+    /// Restrict to entries before an opaque cursor.
+    pub fn before_cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.before = Some(cursor.into());
+        self
+    }
+
+    /// Restrict to the in-game time window `[since_secs, until_secs]`.
+    pub fn in_window(mut self, since_secs: f64, until_secs: f64) -> Self {
+        self.since_secs = Some(since_secs);
+        self.until_secs = Some(until_secs);
+        self
+    }
 }
 
 /// Daemon → Gamepack: Response with match timeline data.
@@ -416,6 +699,231 @@ pub struct GetMatchTimelineRequest {
 pub struct GetMatchTimelineResponse {
     /// Whether the match was found
     pub found: bool,
-    /// Timeline entries (empty if not found)
+    /// Timeline entries (empty if not found), sorted by `game_time_secs`
+    /// then insertion order
     pub entries: Vec<TimelineEntry>,
+    /// Opaque cursor to pass as `before`/`after` on the next request; set
+    /// when more entries exist beyond `limit`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+// ============================================================================
+// GAME SESSIONS
+// ============================================================================
+
+/// A single arbitrary key-value property attached to a `GameSession` (e.g.
+/// lobby type, map, queue, region). Keeps the session model game-agnostic,
+/// the same way subpack stat schemas keep `WriteStats` game-agnostic.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameProperty {
+    /// Property name
+    pub key: String,
+    /// Property value
+    pub value: String,
+}
+
+impl GameProperty {
+    /// Create a new property.
+    pub fn new(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// A single player participating in a `GameSession`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerSession {
+    /// Game's native player/account ID
+    pub player_id: String,
+    /// Human-readable display name
+    pub display_name: String,
+    /// Team identifier, if the game has teams (e.g. "blue", "red", "1")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team: Option<String>,
+}
+
+impl PlayerSession {
+    /// Create a new player session with no team assigned.
+    pub fn new(player_id: impl Into<String>, display_name: impl Into<String>) -> Self {
+        Self {
+            player_id: player_id.into(),
+            display_name: display_name.into(),
+            team: None,
+        }
+    }
+
+    /// Set the player's team.
+    pub fn with_team(mut self, team: impl Into<String>) -> Self {
+        self.team = Some(team.into());
+        self
+    }
+}
+
+/// A concrete game session: the connected game plus who is playing it and
+/// under what free-form metadata.
+///
+/// Fills the gap between `GameStatus` ("connected") and per-match data
+/// (`MatchDataMessage`), and lets the daemon associate clips with specific
+/// players.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSession {
+    /// Game's native session/lobby ID
+    pub session_id: String,
+    /// When the session started (ISO 8601)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
+    /// When the session ended (ISO 8601), if it has
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<String>,
+    /// Arbitrary gamepack-defined metadata (lobby type, map, queue, region)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<GameProperty>,
+    /// Players participating in this session
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub players: Vec<PlayerSession>,
+}
+
+impl GameSession {
+    /// Create a new, in-progress session with no properties or players yet.
+    pub fn new(session_id: impl Into<String>) -> Self {
+        Self {
+            session_id: session_id.into(),
+            started_at: None,
+            ended_at: None,
+            properties: Vec::new(),
+            players: Vec::new(),
+        }
+    }
+
+    /// Set when the session started.
+    pub fn with_started_at(mut self, started_at: impl Into<String>) -> Self {
+        self.started_at = Some(started_at.into());
+        self
+    }
+
+    /// Set when the session ended.
+    pub fn with_ended_at(mut self, ended_at: impl Into<String>) -> Self {
+        self.ended_at = Some(ended_at.into());
+        self
+    }
+
+    /// Append a property.
+    pub fn with_property(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.properties.push(GameProperty::new(key, value));
+        self
+    }
+
+    /// Append a player.
+    pub fn with_player(mut self, player: PlayerSession) -> Self {
+        self.players.push(player);
+        self
+    }
+}
+
+/// Daemon → Gamepack: request the current session for a subpack.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetSessionRequest {
+    /// Subpack index
+    pub subpack: u8,
+}
+
+/// Gamepack → Daemon: response to `GetSessionRequest`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetSessionResponse {
+    /// Current session, or `None` if no session is active
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<GameSession>,
+}
+
+/// Gamepack → Daemon: push an updated session (e.g. a player joined, or
+/// properties changed), unprompted by a `GetSessionRequest`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionUpdate {
+    /// Subpack index
+    pub subpack: u8,
+    /// The updated session
+    pub session: GameSession,
+}
+
+// ============================================================================
+// PROTOCOL ENVELOPE
+// ============================================================================
+
+/// Every message exchangeable between the daemon and a gamepack, tagged by
+/// opcode (`op`) so a single multiplexed transport (stdio, websocket, ...)
+/// can dispatch without out-of-band framing per message kind.
+///
+/// Covers both directions: gamepack-originated messages (`Init`, `Status`,
+/// `MatchData`, `SessionUpdate`), daemon-originated requests paired with their
+/// gamepack responses (`IsMatchInProgress*`, `GetSession*`,
+/// `FetchCanonicalMatch*`), and the gamepack-originated `GetMatchTimeline*`
+/// request answered by the daemon.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ProtocolMessage {
+    /// Gamepack → Daemon: result of the `init` handshake
+    Init(InitResponse),
+    /// Gamepack → Daemon: current connection/game status
+    Status(GameStatus),
+    /// Gamepack → Daemon: a `WriteStats` / `WriteEvents` / `SetComplete` write
+    MatchData(MatchDataMessage),
+    /// Daemon → Gamepack: is this match still in progress?
+    IsMatchInProgressRequest(IsMatchInProgressRequest),
+    /// Gamepack → Daemon: response to `IsMatchInProgressRequest`
+    IsMatchInProgressResponse(IsMatchInProgressResponse),
+    /// Gamepack → Daemon: fetch match timeline entries
+    GetMatchTimelineRequest(GetMatchTimelineRequest),
+    /// Daemon → Gamepack: timeline entries for a `GetMatchTimelineRequest`
+    GetMatchTimelineResponse(GetMatchTimelineResponse),
+    /// Daemon → Gamepack: request the current session for a subpack
+    GetSessionRequest(GetSessionRequest),
+    /// Gamepack → Daemon: response to `GetSessionRequest`
+    GetSessionResponse(GetSessionResponse),
+    /// Gamepack → Daemon: unprompted session change notification
+    SessionUpdate(SessionUpdate),
+    /// Daemon → Gamepack: fetch canonical post-game stats from the official API
+    FetchCanonicalMatch(FetchCanonicalMatch),
+    /// Gamepack → Daemon: response to `FetchCanonicalMatch`
+    FetchCanonicalMatchResponse(FetchCanonicalMatchResponse),
+}
+
+/// Wraps a `ProtocolMessage` with a correlation id and protocol version for
+/// framing over stdio/websocket transports.
+///
+/// A request's `id` is echoed back on its response so the transport can
+/// track in-flight requests (e.g. `IsMatchInProgress` against multiple
+/// subpacks at once) instead of assuming one in-flight message at a time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Envelope {
+    /// Correlation id; a response echoes the id of the request it answers
+    pub id: u64,
+    /// Protocol version the message is framed with (a reply built by
+    /// `respond` carries the request's version)
+    pub protocol_version: u32,
+    /// The wrapped message
+    pub payload: ProtocolMessage,
+}
+
+impl Envelope {
+    /// Wrap a message with a correlation id and protocol version.
+    pub fn new(id: u64, protocol_version: u32, payload: ProtocolMessage) -> Self {
+        Self {
+            id,
+            protocol_version,
+            payload,
+        }
+    }
+
+    /// Build a response envelope that echoes this envelope's `id` and
+    /// `protocol_version`.
+    pub fn respond(&self, payload: ProtocolMessage) -> Self {
+        Self {
+            id: self.id,
+            protocol_version: self.protocol_version,
+            payload,
+        }
+    }
 }